@@ -0,0 +1,129 @@
+use crate::schema::{contests, problems, submissions};
+use crate::{Contest, Problem, Submission};
+
+use bb8::Pool;
+use bb8_diesel::DieselConnectionManager;
+use diesel::dsl::insert_into;
+use diesel::pg::upsert::excluded;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+
+/// Non-blocking counterpart of `SqlClient`, backed by a `bb8` pool so the
+/// crawler's fetch loop can `tokio::join!` a batch insert against the next
+/// page download instead of stalling the executor on diesel's blocking API.
+pub struct AsyncSqlClient {
+    pool: Pool<DieselConnectionManager<PgConnection>>,
+}
+
+impl AsyncSqlClient {
+    pub async fn new(user: &str, pass: &str, host: &str, db: &str) -> Result<Self, String> {
+        let url = format!("postgresql://{}:{}@{}/{}", user, pass, host, db);
+        let manager = DieselConnectionManager::<PgConnection>::new(url);
+        let pool = Pool::builder()
+            .build(manager)
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        Ok(Self { pool })
+    }
+
+    pub async fn insert_submissions(&self, values: &[Submission]) -> Result<usize, String> {
+        let conn = self.pool.get().await.map_err(|e| format!("{:?}", e))?;
+        let values = values.to_vec();
+        conn.run(move |c| {
+            c.transaction(|| {
+                values
+                    .chunks(super::chunk_len(super::SUBMISSION_COLUMNS))
+                    .map(|chunk| {
+                        insert_into(submissions::table)
+                            .values(chunk)
+                            .on_conflict(submissions::id)
+                            .do_update()
+                            .set((
+                                submissions::user_id.eq(excluded(submissions::user_id)),
+                                submissions::result.eq(excluded(submissions::result)),
+                                submissions::point.eq(excluded(submissions::point)),
+                                submissions::execution_time
+                                    .eq(excluded(submissions::execution_time)),
+                                submissions::length.eq(excluded(submissions::length)),
+                                submissions::language.eq(excluded(submissions::language)),
+                                submissions::epoch_second.eq(excluded(submissions::epoch_second)),
+                                submissions::problem_id.eq(excluded(submissions::problem_id)),
+                                submissions::contest_id.eq(excluded(submissions::contest_id)),
+                            ))
+                            .execute(c)
+                    })
+                    .try_fold(0, |acc, result| result.map(|n| acc + n))
+            })
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))
+    }
+
+    pub async fn insert_contests(&self, values: &[Contest]) -> Result<usize, String> {
+        let conn = self.pool.get().await.map_err(|e| format!("{:?}", e))?;
+        let values = values.to_vec();
+        conn.run(move |c| {
+            c.transaction(|| {
+                values
+                    .chunks(super::chunk_len(super::CONTEST_COLUMNS))
+                    .map(|chunk| {
+                        insert_into(contests::table)
+                            .values(chunk)
+                            .on_conflict(contests::id)
+                            .do_nothing()
+                            .execute(c)
+                    })
+                    .try_fold(0, |acc, result| result.map(|n| acc + n))
+            })
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))
+    }
+
+    pub async fn insert_problems(&self, values: &[Problem]) -> Result<usize, String> {
+        let conn = self.pool.get().await.map_err(|e| format!("{:?}", e))?;
+        let values = values.to_vec();
+        conn.run(move |c| {
+            c.transaction(|| {
+                values
+                    .chunks(super::chunk_len(super::PROBLEM_COLUMNS))
+                    .map(|chunk| {
+                        insert_into(problems::table)
+                            .values(chunk)
+                            .on_conflict(problems::id)
+                            .do_nothing()
+                            .execute(c)
+                    })
+                    .try_fold(0, |acc, result| result.map(|n| acc + n))
+            })
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))
+    }
+
+    pub async fn get_problems(&self) -> Result<Vec<Problem>, String> {
+        let conn = self.pool.get().await.map_err(|e| format!("{:?}", e))?;
+        conn.run(|c| problems::dsl::problems.load::<Problem>(c))
+            .await
+            .map_err(|e| format!("{:?}", e))
+    }
+
+    pub async fn get_contests(&self) -> Result<Vec<Contest>, String> {
+        let conn = self.pool.get().await.map_err(|e| format!("{:?}", e))?;
+        conn.run(|c| contests::dsl::contests.load::<Contest>(c))
+            .await
+            .map_err(|e| format!("{:?}", e))
+    }
+
+    pub async fn get_submissions(&self, user_id: &str) -> Result<Vec<Submission>, String> {
+        let conn = self.pool.get().await.map_err(|e| format!("{:?}", e))?;
+        let user_id = user_id.to_owned();
+        conn.run(move |c| {
+            submissions::dsl::submissions
+                .filter(submissions::user_id.eq(user_id))
+                .load::<Submission>(c)
+        })
+        .await
+        .map_err(|e| format!("{:?}", e))
+    }
+}
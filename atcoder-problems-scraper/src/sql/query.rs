@@ -0,0 +1,78 @@
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+
+#[derive(QueryableByName)]
+struct RankingRow {
+    #[sql_type = "Text"]
+    user_id: String,
+    #[sql_type = "BigInt"]
+    count: i64,
+}
+
+/// Ranks users by the number of distinct problems they have solved (AC).
+pub fn accepted_count_ranking(conn: &PgConnection) -> Result<Vec<(String, i64)>, String> {
+    let rows: Vec<RankingRow> = diesel::sql_query(
+        "SELECT user_id, COUNT(DISTINCT problem_id) AS count \
+         FROM submissions \
+         WHERE result = 'AC' \
+         GROUP BY user_id \
+         ORDER BY count DESC, user_id ASC",
+    )
+    .load(conn)
+    .map_err(|e| format!("{:?}", e))?;
+    Ok(rows.into_iter().map(|row| (row.user_id, row.count)).collect())
+}
+
+/// Ranks users by the sum of the highest point value they earned on each
+/// problem they solved (AC).
+pub fn rated_point_sum_ranking(conn: &PgConnection) -> Result<Vec<(String, i64)>, String> {
+    let rows: Vec<RankingRow> = diesel::sql_query(
+        "SELECT user_id, CAST(ROUND(SUM(max_point)) AS BIGINT) AS count \
+         FROM ( \
+             SELECT user_id, problem_id, MAX(point) AS max_point \
+             FROM submissions \
+             WHERE result = 'AC' \
+             GROUP BY user_id, problem_id \
+         ) AS best_per_problem \
+         GROUP BY user_id \
+         ORDER BY count DESC, user_id ASC",
+    )
+    .load(conn)
+    .map_err(|e| format!("{:?}", e))?;
+    Ok(rows.into_iter().map(|row| (row.user_id, row.count)).collect())
+}
+
+/// Ranks users by their longest streak of consecutive JST calendar days
+/// with at least one accepted submission.
+///
+/// The streak itself is computed in one statement with the classic
+/// gaps-and-islands trick: within a run of consecutive days, `day -
+/// ROW_NUMBER()` (ordered by day, per user) is constant, so grouping on
+/// that difference isolates each run without pulling submissions into Rust.
+pub fn longest_streak_ranking(conn: &PgConnection) -> Result<Vec<(String, i64)>, String> {
+    let rows: Vec<RankingRow> = diesel::sql_query(
+        "WITH ac_days AS ( \
+             SELECT DISTINCT user_id, \
+                 CAST(FLOOR((epoch_second + 9 * 3600) / 86400) AS BIGINT) AS day \
+             FROM submissions \
+             WHERE result = 'AC' \
+         ), \
+         islands AS ( \
+             SELECT user_id, day, \
+                 day - ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY day) AS island \
+             FROM ac_days \
+         ) \
+         SELECT user_id, CAST(MAX(streak) AS BIGINT) AS count \
+         FROM ( \
+             SELECT user_id, island, COUNT(*) AS streak \
+             FROM islands \
+             GROUP BY user_id, island \
+         ) AS runs \
+         GROUP BY user_id \
+         ORDER BY count DESC, user_id ASC",
+    )
+    .load(conn)
+    .map_err(|e| format!("{:?}", e))?;
+    Ok(rows.into_iter().map(|row| (row.user_id, row.count)).collect())
+}